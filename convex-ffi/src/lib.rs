@@ -3,13 +3,17 @@ use std::{
     sync::Arc,
 };
 
+use base64::Engine;
 use convex::{ConvexClient, FunctionResult, Value as ConvexValue};
 use futures::{
     channel::oneshot::{self, Sender},
-    pin_mut, select_biased, FutureExt, StreamExt,
+    pin_mut, select_biased,
+    stream::select_all,
+    FutureExt, StreamExt,
 };
 use ordered_float::OrderedFloat;
-use tokio::sync::{MappedMutexGuard, Mutex, MutexGuard};
+use serde_json::{json, Value as JsonValue};
+use tokio::sync::{watch, MappedMutexGuard, Mutex, MutexGuard};
 use uniffi::{
     check_remaining,
     deps::bytes::{Buf, BufMut},
@@ -23,6 +27,24 @@ uniffi::include_scaffolding!("lib");
 pub struct Client {
     deployment_url: String,
     _inner: Mutex<Option<ConvexClient>>,
+    state_tx: watch::Sender<ConnectionState>,
+}
+
+// `convex::ConvexClient` doesn't currently expose a reconnection/transport-state stream to
+// observe, so nothing drives `Reconnecting` yet: this only reports this `Client`'s own
+// `connect()`/`close()` lifecycle, not the underlying websocket's. The variant is kept to
+// match the requested `{ Connected, Reconnecting, Disconnected }` shape so existing host-side
+// `match`es over `ConnectionState` don't need to change once a real reconnect signal lands.
+#[derive(uniffi::Enum, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ConnectionState {
+    Connected,
+    Reconnecting,
+    Disconnected,
+}
+
+#[uniffi::export(callback_interface)]
+pub trait ConnectionObserver: Send + Sync {
+    fn on_state_change(&self, state: ConnectionState);
 }
 
 type Float64 = OrderedFloat<f64>;
@@ -144,11 +166,30 @@ unsafe impl FfiConverter<UT> for ValueSet {
 #[uniffi::export(callback_interface)]
 pub trait Callback: Send + Sync {
     fn update(&self, value: Value);
+    fn on_error(&self, error: ConvexError);
+    fn on_complete(&self);
 }
 
-#[derive(uniffi::Error)]
-pub enum SubscribeError {
-    Generic { message: String },
+#[derive(uniffi::Record)]
+pub struct QuerySpec {
+    pub path: String,
+    pub args: ValueObject,
+}
+
+#[derive(uniffi::Error, Debug)]
+pub enum ConvexError {
+    ConnectionError { message: String },
+    ServerError { message: String },
+    NotConnected,
+    InvalidArgs { message: String },
+}
+
+impl From<anyhow::Error> for ConvexError {
+    fn from(error: anyhow::Error) -> Self {
+        ConvexError::ConnectionError {
+            message: error.to_string(),
+        }
+    }
 }
 
 #[uniffi::export]
@@ -160,26 +201,53 @@ fn set_tracing_subscriber() {
 impl Client {
     #[uniffi::constructor]
     fn new(deployment_url: String) -> Arc<Self> {
+        let (state_tx, _) = watch::channel(ConnectionState::Disconnected);
         Arc::new(Self {
             deployment_url,
             _inner: Mutex::new(None),
+            state_tx,
         })
     }
 }
 
 #[uniffi::export(async_runtime = "tokio")]
 impl Client {
-    async fn connect(&self) {
+    async fn connect(&self) -> Result<(), ConvexError> {
         let mut inner = self._inner.lock().await;
 
-        let client = ConvexClient::new(&self.deployment_url).await;
-        if let Ok(client) = client {
-            inner.replace(client);
-        }
+        let client = ConvexClient::new(&self.deployment_url).await?;
+        inner.replace(client);
+        self.state_tx.send_replace(ConnectionState::Connected);
+
+        Ok(())
     }
 
     async fn close(&self) {
         self._inner.lock().await.take();
+        self.state_tx.send_replace(ConnectionState::Disconnected);
+    }
+
+    pub async fn set_auth(&self, token: Option<String>) -> Result<(), ConvexError> {
+        let mut client = self.client().await?;
+        client.set_auth(token).await;
+
+        Ok(())
+    }
+
+    /// Observes this `Client`'s `connect()`/`close()` lifecycle, invoking `observer` once
+    /// immediately with the current state and again after each later change, with the state
+    /// current at that point: transitions in quick succession may be reported only once, as the
+    /// last of them. This
+    /// does not observe the underlying websocket's own reconnect attempts: `convex::ConvexClient`
+    /// exposes no such signal today, so a transport-level drop-and-reconnect is invisible here.
+    pub async fn observe_connection_state(&self, observer: Box<dyn ConnectionObserver>) {
+        let mut state_rx = self.state_tx.subscribe();
+        tokio::spawn(async move {
+            observer.on_state_change(*state_rx.borrow_and_update());
+            while state_rx.changed().await.is_ok() {
+                observer.on_state_change(*state_rx.borrow_and_update());
+            }
+        });
     }
 
     pub async fn subscribe(
@@ -187,11 +255,11 @@ impl Client {
         path: String,
         args: ValueObject,
         callback: Box<dyn Callback>,
-    ) -> Result<Arc<Subscription>, SubscribeError> {
+    ) -> Result<Arc<Subscription>, ConvexError> {
         let mut client = self.client().await?;
         let args = to_convex_args(args);
 
-        let mut subscription = client.subscribe(&path, args).await.unwrap();
+        let mut subscription = client.subscribe(&path, args).await?;
 
         let (sender, receiver) = oneshot::channel::<()>();
 
@@ -202,14 +270,88 @@ impl Client {
             loop {
                 select_biased! {
                     result = subscription.next().fuse() => {
-                        if let Some(result) = result {
-                            match result {
-                                FunctionResult::Value(value) => {
-                                    callback.update(value.into());
-                                }
-                                FunctionResult::ErrorMessage(message) => {
-                                    tracing::error!("Subscription error: {}", message);
-                                }
+                        match result {
+                            Some(FunctionResult::Value(value)) => {
+                                callback.update(value.into());
+                            }
+                            Some(FunctionResult::ErrorMessage(message)) => {
+                                callback.on_error(ConvexError::ServerError { message });
+                            }
+                            None => {
+                                callback.on_complete();
+                                break
+                            }
+                        }
+                    },
+                    _ = unsubscribe_fut => {
+                        break
+                    }
+                }
+            }
+        });
+
+        Ok(Arc::new(Subscription {
+            sender: std::sync::Mutex::new(Some(sender)),
+        }))
+    }
+
+    /// Subscribes to a set of queries, delivering the combined results keyed by each query's
+    /// `path`. `convex::ConvexClient` has no joint query-set subscription API, so this merges
+    /// N independent single-query subscriptions: it is a best-effort combination with no
+    /// cross-query snapshot-consistency guarantee, and two updates delivered back-to-back may
+    /// reflect different server snapshots. `path`s must be unique across `queries`.
+    pub async fn subscribe_many(
+        &self,
+        queries: Vec<QuerySpec>,
+        callback: Box<dyn Callback>,
+    ) -> Result<Arc<Subscription>, ConvexError> {
+        let mut client = self.client().await?;
+
+        let mut seen_paths = BTreeSet::new();
+        let mut paths = Vec::with_capacity(queries.len());
+        let mut subscriptions = Vec::with_capacity(queries.len());
+        for query in queries {
+            if !seen_paths.insert(query.path.clone()) {
+                return Err(ConvexError::InvalidArgs {
+                    message: format!("duplicate query path in subscribe_many: {}", query.path),
+                });
+            }
+            let args = to_convex_args(query.args);
+            let subscription = client.subscribe(&query.path, args).await?;
+            paths.push(query.path);
+            subscriptions.push(subscription);
+        }
+
+        let mut combined = select_all(
+            subscriptions
+                .into_iter()
+                .enumerate()
+                .map(|(index, subscription)| {
+                    subscription.map(move |result| (index, result)).boxed()
+                }),
+        );
+
+        let (sender, receiver) = oneshot::channel::<()>();
+
+        let unsubscribe_fut = receiver.fuse();
+
+        tokio::spawn(async move {
+            pin_mut!(unsubscribe_fut);
+            let mut latest = ValueObject::new();
+            loop {
+                select_biased! {
+                    result = combined.next().fuse() => {
+                        match result {
+                            Some((index, FunctionResult::Value(value))) => {
+                                latest.insert(paths[index].clone(), value.into());
+                                callback.update(Value::Object { value: latest.clone() });
+                            }
+                            Some((_, FunctionResult::ErrorMessage(message))) => {
+                                callback.on_error(ConvexError::ServerError { message });
+                            }
+                            None => {
+                                callback.on_complete();
+                                break
                             }
                         }
                     },
@@ -225,49 +367,47 @@ impl Client {
         }))
     }
 
-    pub async fn query(&self, path: String, args: ValueObject) -> Result<Value, SubscribeError> {
+    pub async fn query(&self, path: String, args: ValueObject) -> Result<Value, ConvexError> {
         let mut client = self.client().await?;
         let args = to_convex_args(args);
 
-        let result = client.query(&path, args).await.unwrap();
+        let result = client.query(&path, args).await?;
         match result {
             FunctionResult::Value(value) => Ok(value.into()),
-            FunctionResult::ErrorMessage(message) => Err(SubscribeError::Generic { message }),
+            FunctionResult::ErrorMessage(message) => Err(ConvexError::ServerError { message }),
         }
     }
 
-    pub async fn mutation(&self, path: String, args: ValueObject) -> Result<Value, SubscribeError> {
+    pub async fn mutation(&self, path: String, args: ValueObject) -> Result<Value, ConvexError> {
         let mut client = self.client().await?;
         let args = to_convex_args(args);
 
-        let result = client.mutation(&path, args).await.unwrap();
+        let result = client.mutation(&path, args).await?;
         match result {
             FunctionResult::Value(value) => Ok(value.into()),
-            FunctionResult::ErrorMessage(message) => Err(SubscribeError::Generic { message }),
+            FunctionResult::ErrorMessage(message) => Err(ConvexError::ServerError { message }),
         }
     }
 
-    pub async fn action(&self, path: String, args: ValueObject) -> Result<Value, SubscribeError> {
+    pub async fn action(&self, path: String, args: ValueObject) -> Result<Value, ConvexError> {
         let mut client = self.client().await?;
         let args = to_convex_args(args);
 
-        let result = client.action(&path, args).await.unwrap();
+        let result = client.action(&path, args).await?;
         match result {
             FunctionResult::Value(value) => Ok(value.into()),
-            FunctionResult::ErrorMessage(message) => Err(SubscribeError::Generic { message }),
+            FunctionResult::ErrorMessage(message) => Err(ConvexError::ServerError { message }),
         }
     }
 }
 
 impl Client {
-    async fn client(&self) -> Result<MappedMutexGuard<ConvexClient>, SubscribeError> {
+    async fn client(&self) -> Result<MappedMutexGuard<ConvexClient>, ConvexError> {
         let lock = self._inner.lock().await;
         if lock.is_some() {
             Ok(MutexGuard::map(lock, |lock| lock.as_mut().unwrap()))
         } else {
-            Err(SubscribeError::Generic {
-                message: "No client set".to_string(),
-            })
+            Err(ConvexError::NotConnected)
         }
     }
 }
@@ -380,3 +520,180 @@ impl From<Value> for ConvexValue {
         }
     }
 }
+
+/// Serializes `value` to this crate's own tagged JSON encoding (see [`value_to_json_value`]),
+/// not Convex's untagged JSON export/REST format: `value_from_json(value_to_json(v)) == v` for
+/// every `Value`, which the untagged export format cannot guarantee (it can't tell an `Int`
+/// from a `Float`, or represent `Bytes`/`Set`/`Map` at all). Use this to persist a `Value` or
+/// pass it between host languages that already speak JSON; do not feed it Convex REST payloads.
+#[uniffi::export]
+fn value_to_json(value: Value) -> Result<String, ConvexError> {
+    serde_json::to_string(&value_to_json_value(value)).map_err(|error| ConvexError::InvalidArgs {
+        message: error.to_string(),
+    })
+}
+
+/// Parses this crate's own tagged JSON encoding produced by [`value_to_json`]. This is not a
+/// Convex JSON export/REST parser and will reject both bare JSON numbers and untagged objects;
+/// see [`value_to_json_value`] for the encoding.
+#[uniffi::export]
+fn value_from_json(json: String) -> Result<Value, ConvexError> {
+    let json: JsonValue =
+        serde_json::from_str(&json).map_err(|error| ConvexError::InvalidArgs {
+            message: error.to_string(),
+        })?;
+    json_value_to_value(json).map_err(|message| ConvexError::InvalidArgs { message })
+}
+
+// This is a private, round-trip-only tagged encoding, not Convex's JSON export/REST format:
+// plain JSON numbers can't tell an `Int` from a `Float`, and that format can't represent
+// `Bytes`, `Set` or `Map` at all, so round-trippable variants are wrapped in a single-key
+// `$tag` object. `Object` is wrapped under `$obj` too, so a user `Object` whose lone key
+// happens to be e.g. `"$id"` can never be mistaken for a tag. Plain JSON numbers and untagged
+// objects are therefore never produced or accepted on their own.
+fn value_to_json_value(value: Value) -> JsonValue {
+    match value {
+        Value::Id { id } => json!({ "$id": id }),
+        Value::Null => JsonValue::Null,
+        Value::Int { value } => json!({ "$int64": value.to_string() }),
+        Value::Float { value } => {
+            let value = value.0;
+            if value.is_nan() {
+                json!({ "$float64": "NaN" })
+            } else if value == f64::INFINITY {
+                json!({ "$float64": "Infinity" })
+            } else if value == f64::NEG_INFINITY {
+                json!({ "$float64": "-Infinity" })
+            } else {
+                json!({ "$float64": value })
+            }
+        }
+        Value::Bool { value } => JsonValue::Bool(value),
+        Value::String { value } => JsonValue::String(value),
+        Value::Bytes { value } => {
+            json!({ "$bytes": base64::engine::general_purpose::STANDARD.encode(value) })
+        }
+        Value::Array { value } => {
+            JsonValue::Array(value.into_iter().map(value_to_json_value).collect())
+        }
+        Value::Set { value } => {
+            json!({ "$set": value.into_iter().map(value_to_json_value).collect::<Vec<_>>() })
+        }
+        Value::Map { value } => json!({
+            "$map": value
+                .into_iter()
+                .map(|(key, value)| {
+                    JsonValue::Array(vec![value_to_json_value(key), value_to_json_value(value)])
+                })
+                .collect::<Vec<_>>()
+        }),
+        Value::Object { value } => json!({
+            "$obj": JsonValue::Object(
+                value
+                    .into_iter()
+                    .map(|(key, value)| (key, value_to_json_value(value)))
+                    .collect(),
+            )
+        }),
+    }
+}
+
+fn json_value_to_value(json: JsonValue) -> Result<Value, String> {
+    match json {
+        JsonValue::Null => Ok(Value::Null),
+        JsonValue::Bool(value) => Ok(Value::Bool { value }),
+        JsonValue::String(value) => Ok(Value::String { value }),
+        JsonValue::Number(number) => Err(format!(
+            "bare JSON numbers are ambiguous, expected a tagged $int64 or $float64 object: {number}"
+        )),
+        JsonValue::Array(values) => Ok(Value::Array {
+            value: values
+                .into_iter()
+                .map(json_value_to_value)
+                .collect::<Result<_, _>>()?,
+        }),
+        JsonValue::Object(mut object) if object.len() == 1 && object.contains_key("$id") => {
+            let id = object.remove("$id").unwrap();
+            let id = id.as_str().ok_or("$id must be a string")?.to_string();
+            Ok(Value::Id { id })
+        }
+        JsonValue::Object(mut object) if object.len() == 1 && object.contains_key("$int64") => {
+            let value = object.remove("$int64").unwrap();
+            let value = value.as_str().ok_or("$int64 must be a string")?;
+            let value = value.parse::<i64>().map_err(|error| error.to_string())?;
+            Ok(Value::Int { value })
+        }
+        JsonValue::Object(mut object) if object.len() == 1 && object.contains_key("$float64") => {
+            let value = match object.remove("$float64").unwrap() {
+                JsonValue::Number(number) => number.as_f64().ok_or("$float64 must be a number")?,
+                JsonValue::String(value) => match value.as_str() {
+                    "NaN" => f64::NAN,
+                    "Infinity" => f64::INFINITY,
+                    "-Infinity" => f64::NEG_INFINITY,
+                    other => return Err(format!("invalid $float64 value: {other}")),
+                },
+                _ => return Err("$float64 must be a number or string".to_string()),
+            };
+            Ok(Value::Float {
+                value: OrderedFloat(value),
+            })
+        }
+        JsonValue::Object(mut object) if object.len() == 1 && object.contains_key("$bytes") => {
+            let value = object.remove("$bytes").unwrap();
+            let value = value.as_str().ok_or("$bytes must be a string")?;
+            let value = base64::engine::general_purpose::STANDARD
+                .decode(value)
+                .map_err(|error| error.to_string())?;
+            Ok(Value::Bytes { value })
+        }
+        JsonValue::Object(mut object) if object.len() == 1 && object.contains_key("$set") => {
+            let values = object.remove("$set").unwrap();
+            let values = match values {
+                JsonValue::Array(values) => values,
+                _ => return Err("$set must be an array".to_string()),
+            };
+            let value = values
+                .into_iter()
+                .map(json_value_to_value)
+                .collect::<Result<_, _>>()?;
+            Ok(Value::Set { value })
+        }
+        JsonValue::Object(mut object) if object.len() == 1 && object.contains_key("$map") => {
+            let entries = object.remove("$map").unwrap();
+            let entries = match entries {
+                JsonValue::Array(entries) => entries,
+                _ => return Err("$map must be an array of [key, value] pairs".to_string()),
+            };
+            let mut value = ValueMap::new();
+            for entry in entries {
+                let mut pair = match entry {
+                    JsonValue::Array(pair) if pair.len() == 2 => pair,
+                    _ => return Err("$map entries must be [key, value] pairs".to_string()),
+                };
+                let value_json = pair.pop().unwrap();
+                let key_json = pair.pop().unwrap();
+                value.insert(
+                    json_value_to_value(key_json)?,
+                    json_value_to_value(value_json)?,
+                );
+            }
+            Ok(Value::Map { value })
+        }
+        JsonValue::Object(mut object) if object.len() == 1 && object.contains_key("$obj") => {
+            let fields = object.remove("$obj").unwrap();
+            let fields = match fields {
+                JsonValue::Object(fields) => fields,
+                _ => return Err("$obj must be an object".to_string()),
+            };
+            let value = fields
+                .into_iter()
+                .map(|(key, value)| json_value_to_value(value).map(|value| (key, value)))
+                .collect::<Result<_, _>>()?;
+            Ok(Value::Object { value })
+        }
+        JsonValue::Object(_) => Err(
+            "expected a tagged object ($id, $int64, $float64, $bytes, $set, $map or $obj)"
+                .to_string(),
+        ),
+    }
+}
@@ -0,0 +1,166 @@
+use std::fs;
+
+use anyhow::Result;
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::Subcommand;
+use uniffi_bindgen::bindings::TargetLanguage;
+use xshell::{cmd, Shell};
+
+use crate::{utils, workspace};
+
+#[derive(Subcommand)]
+pub enum KotlinCommand {
+    /// Builds the Kotlin AAR.
+    #[command(name = "build-aar")]
+    BuildAar {
+        /// Build with the release profile
+        #[clap(long)]
+        release: bool,
+    },
+}
+
+impl KotlinCommand {
+    pub fn run(self) -> Result<()> {
+        let sh = Shell::new()?;
+        let _d = sh.push_dir(workspace::metadata()?.root_dir);
+        match self {
+            KotlinCommand::BuildAar { release } => {
+                let profile = if release { "release" } else { "dev" };
+                build_aar(&sh, profile)
+            }
+        }
+    }
+}
+
+struct AndroidTarget {
+    rust_target: &'static str,
+    jni_abi: &'static str,
+}
+
+const ANDROID_TARGETS: &[AndroidTarget] = &[
+    AndroidTarget {
+        rust_target: "aarch64-linux-android",
+        jni_abi: "arm64-v8a",
+    },
+    AndroidTarget {
+        rust_target: "armv7-linux-androideabi",
+        jni_abi: "armeabi-v7a",
+    },
+    AndroidTarget {
+        rust_target: "x86_64-linux-android",
+        jni_abi: "x86_64",
+    },
+    AndroidTarget {
+        rust_target: "i686-linux-android",
+        jni_abi: "x86",
+    },
+];
+
+fn build_aar(sh: &Shell, profile: &str) -> Result<()> {
+    let cargo = utils::cargo_path();
+    let workspace::Metadata {
+        root_dir,
+        target_dir,
+    } = workspace::metadata()?;
+
+    let generated_dir = root_dir.join("generated");
+    let templates_dir = root_dir.join("templates");
+
+    let kotlin_dir = generated_dir.join("kotlin");
+    if fs::metadata(&kotlin_dir).is_ok() {
+        fs::remove_dir_all(kotlin_dir.as_path())?;
+    }
+
+    let tmp_dir = kotlin_dir.join("tmp");
+
+    let uniffi_dir = tmp_dir.join("uniffi");
+    // A standard Android library module: Gradle (not us) turns this into a real AAR, with
+    // `classes.jar` compiled from the Kotlin sources and these `jniLibs/<abi>/` merged into
+    // the archive's `jni/<abi>/` — hand-zipping this source tree would not produce a valid AAR.
+    let module_dir = tmp_dir.join("module");
+    let jni_libs_dir = module_dir.join("src/main/jniLibs");
+    let manifest_dir = module_dir.join("src/main");
+    fs::create_dir_all(uniffi_dir.clone())?;
+    fs::create_dir_all(jni_libs_dir.clone())?;
+    fs::create_dir_all(manifest_dir.clone())?;
+
+    let profile_dir_name = if profile == "dev" { "debug" } else { profile };
+
+    println!("Building libraries for Kotlin/Android.");
+    let mut cmd = cmd!(sh, "{cargo} build -p convex-ffi --profile {profile}");
+    // Remove debug info in release mode like Mozilla
+    // see: https://github.com/mozilla/application-services/blob/77e45817376b43586205bd1f58ea847a5472eda0/megazords/ios-rust/build-xcframework.sh#L67-L69
+    if profile == "release" {
+        cmd = cmd.env("RUSTFLAGS", "-C debuginfo=0");
+    }
+    for android_target in ANDROID_TARGETS {
+        cmd = cmd.arg("--target").arg(android_target.rust_target);
+    }
+    cmd.run()?;
+
+    println!("Laying out jniLibs");
+    for android_target in ANDROID_TARGETS {
+        let abi_dir = jni_libs_dir.join(android_target.jni_abi);
+        fs::create_dir_all(&abi_dir)?;
+        fs::copy(
+            target_dir
+                .join(android_target.rust_target)
+                .join(profile_dir_name)
+                .join("libconvex_ffi.so"),
+            abi_dir.join("libconvex_ffi.so"),
+        )?;
+    }
+
+    println!("Generating uniffi files");
+    let udl_file = Utf8PathBuf::from_path_buf(root_dir.join("convex-ffi/src/lib.udl")).unwrap();
+    let out_dir = Utf8Path::from_path(&uniffi_dir).unwrap();
+    // Necessary to extract uniffi interface definition from code, see: https://mozilla.github.io/uniffi-rs/proc_macro/index.html
+    let lib_file = Utf8PathBuf::from_path_buf(
+        target_dir
+            .join(ANDROID_TARGETS[0].rust_target)
+            .join(profile_dir_name)
+            .join("libconvex_ffi.so"),
+    )
+    .unwrap();
+    uniffi_bindgen::generate_bindings(
+        udl_file.as_path(),
+        None,
+        vec![TargetLanguage::Kotlin],
+        Some(out_dir),
+        Some(lib_file.as_path()),
+        false,
+    )?;
+
+    let kotlin_src_dir = module_dir.join("src/main/java");
+    fs::create_dir_all(&kotlin_src_dir)?;
+    utils::copy_dir_all(uniffi_dir.join("uniffi"), kotlin_src_dir.join("uniffi"))?;
+
+    fs::copy(
+        templates_dir.join("build.gradle"),
+        module_dir.join("build.gradle"),
+    )?;
+    fs::copy(
+        templates_dir.join("AndroidManifest.xml"),
+        manifest_dir.join("AndroidManifest.xml"),
+    )?;
+
+    println!("Assembling ConvexFFI AAR with Gradle");
+    let gradle_task = if profile == "release" {
+        "assembleRelease"
+    } else {
+        "assembleDebug"
+    };
+    {
+        let _d = sh.push_dir(&module_dir);
+        cmd!(sh, "gradle {gradle_task}").run()?;
+    }
+
+    let built_aar = module_dir
+        .join("build/outputs/aar")
+        .join(format!("module-{profile_dir_name}.aar"));
+    fs::copy(built_aar, kotlin_dir.join("ConvexFFI.aar"))?;
+
+    fs::remove_dir_all(tmp_dir.as_path())?;
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use clap::Parser;
+use kotlin::KotlinCommand;
 use swift::SwiftCommand;
 
+mod kotlin;
 mod swift;
 mod utils;
 mod workspace;
@@ -11,10 +13,13 @@ mod workspace;
 enum Cli {
     #[command(subcommand)]
     Swift(SwiftCommand),
+    #[command(subcommand)]
+    Kotlin(KotlinCommand),
 }
 
 fn main() -> Result<(), anyhow::Error> {
     match Cli::parse() {
         Cli::Swift(cmd) => cmd.run(),
+        Cli::Kotlin(cmd) => cmd.run(),
     }
 }